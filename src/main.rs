@@ -1,11 +1,22 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use colored::*;
+use glob::Pattern;
+use rayon::prelude::*;
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use walkdir::WalkDir;
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
+}
+
 #[derive(Parser)]
 #[command(name = "fdc")]
 #[command(about = "Find Dead Code - Identifies unused files in WordPress plugin projects")]
@@ -18,6 +29,23 @@ struct Cli {
 
     #[arg(short, long, help = "Show verbose output")]
     verbose: bool,
+
+    #[arg(long = "exclude", value_name = "GLOB", help = "Skip paths matching this glob (repeatable)")]
+    exclude: Vec<String>,
+
+    #[arg(long = "include", value_name = "GLOB", help = "Only scan paths matching this glob (repeatable)")]
+    include: Vec<String>,
+
+    #[arg(long, value_enum, default_value = "text", help = "Output format for CI consumption")]
+    format: OutputFormat,
+
+    #[arg(
+        long = "ext",
+        value_name = "EXT,EXT,...",
+        value_delimiter = ',',
+        help = "Comma-separated extensions to scan (default: php,js,css)"
+    )]
+    ext: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone)]
@@ -33,16 +61,28 @@ enum FileType {
     Php,
     JavaScript,
     Css,
+    // Any configured extension that isn't one of the above - still
+    // discovered and checked for liveness, just never parsed for outgoing
+    // references (e.g. `.svg`, or an unrecognized asset type).
+    Other(String),
 }
 
 impl FileType {
-    fn from_extension(ext: &str) -> Option<Self> {
-        match ext.to_lowercase().as_str() {
-            "php" => Some(Self::Php),
-            "js" => Some(Self::JavaScript),
-            "css" => Some(Self::Css),
-            _ => None,
+    /// Classifies an extension into a `FileType`, but only if it's part of
+    /// the configured extension set - this is what lets `--ext` narrow or
+    /// widen what gets scanned.
+    fn from_extension(ext: &str, extensions: &ExtensionConfig) -> Option<Self> {
+        let ext = ext.to_lowercase();
+        if !extensions.allows(&ext) {
+            return None;
         }
+
+        Some(match ext.as_str() {
+            "php" => Self::Php,
+            "js" | "mjs" | "cjs" | "jsx" | "ts" | "tsx" => Self::JavaScript,
+            "css" | "scss" | "sass" | "less" => Self::Css,
+            other => Self::Other(other.to_string()),
+        })
     }
 
     fn extensions(&self) -> &[&str] {
@@ -50,14 +90,461 @@ impl FileType {
             Self::Php => &["php"],
             Self::JavaScript => &["js"],
             Self::Css => &["css"],
+            Self::Other(_) => &[],
+        }
+    }
+}
+
+/// The set of extensions `discover_files` will recognize. Defaults to the
+/// extensions of the built-in `FileType` variants; `--ext` replaces it
+/// entirely so users can scan any asset type their plugin links to.
+struct ExtensionConfig {
+    allowed: HashSet<String>,
+}
+
+impl ExtensionConfig {
+    fn from_cli(ext: &Option<Vec<String>>) -> Self {
+        let allowed = match ext {
+            Some(exts) => exts
+                .iter()
+                .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+                .filter(|e| !e.is_empty())
+                .collect(),
+            None => [FileType::Php, FileType::JavaScript, FileType::Css]
+                .iter()
+                .flat_map(|file_type| file_type.extensions())
+                .map(|ext| ext.to_string())
+                .collect(),
+        };
+
+        Self { allowed }
+    }
+
+    fn allows(&self, ext: &str) -> bool {
+        self.allowed.contains(ext)
+    }
+}
+
+/// Patterns that decide which paths `discover_files` walks into at all.
+/// Excludes are checked during the `WalkDir` traversal so an excluded
+/// directory (e.g. `node_modules`) is never descended into in the first
+/// place, rather than being walked and filtered out afterwards.
+struct ScanConfig {
+    extensions: ExtensionConfig,
+    exclude: Vec<Pattern>,
+    include: Vec<Pattern>,
+    root_path: PathBuf,
+}
+
+impl ScanConfig {
+    // Always skipped, even without an explicit --exclude: these directories
+    // are never where a WordPress plugin's own source lives.
+    const DEFAULT_EXCLUDES: &'static [&'static str] = &["**/.git/**", "**/node_modules/**", "**/vendor/**"];
+
+    fn from_cli(cli: &Cli, root_path: &Path) -> Result<Self, glob::PatternError> {
+        let mut exclude = Vec::new();
+        for pattern in Self::DEFAULT_EXCLUDES {
+            exclude.push(Pattern::new(pattern)?);
+        }
+        for pattern in &cli.exclude {
+            exclude.push(Pattern::new(pattern)?);
+        }
+
+        let mut include = Vec::new();
+        for pattern in &cli.include {
+            include.push(Pattern::new(pattern)?);
+        }
+
+        Ok(Self {
+            extensions: ExtensionConfig::from_cli(&cli.ext),
+            exclude,
+            include,
+            root_path: root_path.to_path_buf(),
+        })
+    }
+
+    /// Globs are written relative to the scan root (e.g. `vendor/**`), but
+    /// `WalkDir` hands us absolute, canonicalized paths - strip the root
+    /// prefix before matching so a user's glob matches what they typed.
+    fn relative_to_root<'a>(&self, path: &'a Path) -> &'a Path {
+        path.strip_prefix(&self.root_path).unwrap_or(path)
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        let relative = self.relative_to_root(path);
+        self.exclude.iter().any(|pattern| pattern.matches_path(relative))
+    }
+
+    fn is_included(&self, path: &Path) -> bool {
+        if self.include.is_empty() {
+            return true;
+        }
+        let relative = self.relative_to_root(path);
+        self.include.iter().any(|pattern| pattern.matches_path(relative))
+    }
+}
+
+#[cfg(test)]
+mod scan_config_tests {
+    use super::*;
+
+    fn config(root: &str, exclude: &[&str], include: &[&str]) -> ScanConfig {
+        let cli = Cli {
+            path: None,
+            delete: false,
+            verbose: false,
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            include: include.iter().map(|s| s.to_string()).collect(),
+            format: OutputFormat::Text,
+            ext: None,
+        };
+        ScanConfig::from_cli(&cli, Path::new(root)).unwrap()
+    }
+
+    #[test]
+    fn exclude_glob_is_matched_relative_to_root() {
+        let cfg = config("/project", &["thirdparty/**"], &[]);
+        assert!(cfg.is_excluded(Path::new("/project/thirdparty/lib.php")));
+        assert!(!cfg.is_excluded(Path::new("/project/includes/lib.php")));
+    }
+
+    #[test]
+    fn include_glob_is_matched_relative_to_root() {
+        let cfg = config("/project", &[], &["thirdparty/**"]);
+        assert!(cfg.is_included(Path::new("/project/thirdparty/lib.php")));
+        assert!(!cfg.is_included(Path::new("/project/includes/lib.php")));
+    }
+
+    #[test]
+    fn default_excludes_skip_vcs_and_dependency_dirs() {
+        let cfg = config("/project", &[], &[]);
+        assert!(cfg.is_excluded(Path::new("/project/.git/HEAD")));
+        assert!(cfg.is_excluded(Path::new("/project/node_modules/pkg/index.js")));
+        assert!(cfg.is_excluded(Path::new("/project/vendor/autoload.php")));
+        assert!(!cfg.is_excluded(Path::new("/project/includes/core.php")));
+    }
+
+    #[test]
+    fn empty_include_list_includes_everything() {
+        let cfg = config("/project", &[], &[]);
+        assert!(cfg.is_included(Path::new("/project/includes/core.php")));
+    }
+}
+
+/// Extracts the literal pieces of a single-quoted/double-quoted string
+/// expression, in order, and concatenates them. This lets us follow paths
+/// built the way WordPress code usually builds them, e.g.
+/// `plugin_dir_path(__FILE__) . 'includes/admin.php'`, where the constant
+/// part (`plugin_dir_path(__FILE__)`, `__DIR__`, `get_template_directory()`)
+/// contributes no literal text and the real path lives in the string literal.
+fn concat_string_literals(expr: &str, literal_pattern: &Regex) -> String {
+    literal_pattern
+        .captures_iter(expr)
+        .map(|cap| cap[1].to_string())
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+/// Splits a PHP function-call argument list on top-level commas, ignoring
+/// commas nested inside parentheses so `plugin_dir_url(__FILE__) . 'x.js'`
+/// isn't split in the middle of the function call.
+fn split_top_level_args(args: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+
+    for ch in args.chars() {
+        match ch {
+            '(' | '[' => {
+                depth += 1;
+                current.push(ch);
+            }
+            ')' | ']' => {
+                depth -= 1;
+                current.push(ch);
+            }
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(ch),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts
+}
+
+/// Pulls the paths this PHP source actually links to: `include`/`include_once`/
+/// `require`/`require_once` targets, and the src/stylesheet argument of
+/// `wp_enqueue_script`/`wp_enqueue_style`/`wp_register_script`/`wp_register_style`
+/// calls. Each result is the raw (still relative, possibly concatenated) path
+/// string as written in the source; callers resolve it with `resolve_path`.
+fn extract_php_link_targets(content: &str) -> Vec<String> {
+    let literal_pattern = Regex::new(r#"['"]([^'"]*)['"]"#).unwrap();
+    let include_pattern = Regex::new(r"(?:include|require)(?:_once)?\s*\(?\s*([^;]+?)\)?\s*;").unwrap();
+    let enqueue_pattern = Regex::new(
+        r"\b(?:wp_enqueue_script|wp_enqueue_style|wp_register_script|wp_register_style)\s*\(([^;]*?)\)\s*;",
+    )
+    .unwrap();
+
+    let mut targets = Vec::new();
+
+    for cap in include_pattern.captures_iter(content) {
+        let path = concat_string_literals(&cap[1], &literal_pattern);
+        if !path.is_empty() {
+            targets.push(path);
+        }
+    }
+
+    for cap in enqueue_pattern.captures_iter(content) {
+        let args = split_top_level_args(&cap[1]);
+        // Signature is (handle, src, ...) - the handle itself isn't a path.
+        if let Some(src_arg) = args.get(1) {
+            let path = concat_string_literals(src_arg, &literal_pattern);
+            if !path.is_empty() {
+                targets.push(path);
+            }
         }
     }
+
+    targets
+}
+
+#[cfg(test)]
+mod extract_php_link_targets_tests {
+    use super::*;
+
+    #[test]
+    fn finds_include_and_require_targets() {
+        let content = r#"
+            include 'header.php';
+            require_once("inc/footer.php");
+        "#;
+
+        let targets = extract_php_link_targets(content);
+
+        assert_eq!(targets, vec!["header.php", "inc/footer.php"]);
+    }
+
+    #[test]
+    fn concatenates_string_literals_in_include_path() {
+        let content = r#"include(PLUGIN_DIR . 'inc/' . 'settings.php');"#;
+
+        let targets = extract_php_link_targets(content);
+
+        assert_eq!(targets, vec!["inc/settings.php"]);
+    }
+
+    #[test]
+    fn finds_enqueue_src_argument_not_handle() {
+        let content = r#"wp_enqueue_script('my-plugin-admin', 'js/admin.js', array('jquery'), '1.0', true);"#;
+
+        let targets = extract_php_link_targets(content);
+
+        assert_eq!(targets, vec!["js/admin.js"]);
+    }
+}
+
+/// Pulls the specifiers out of `import`/`export ... from`/`require(...)`
+/// statements in a JS file. Bundler-style `require` calls and ES module
+/// imports and re-exports are all covered since plugin front-ends mix both.
+fn extract_js_link_targets(content: &str) -> Vec<String> {
+    let import_pattern = Regex::new(r#"import\s+(?:[^'";]+?\sfrom\s+)?['"]([^'"]+)['"]"#).unwrap();
+    let export_from_pattern = Regex::new(r#"export\s+[^'";]+?\sfrom\s+['"]([^'"]+)['"]"#).unwrap();
+    let require_pattern = Regex::new(r#"\brequire\s*\(\s*['"]([^'"]+)['"]\s*\)"#).unwrap();
+
+    let mut targets = Vec::new();
+    for pattern in [&import_pattern, &export_from_pattern, &require_pattern] {
+        for cap in pattern.captures_iter(content) {
+            targets.push(cap[1].to_string());
+        }
+    }
+    targets
+}
+
+/// Pulls `@import` and `url(...)` references out of a CSS file. Remote and
+/// data URLs are skipped since they never resolve to a file in the project.
+fn extract_css_link_targets(content: &str) -> Vec<String> {
+    let import_pattern = Regex::new(r#"@import\s+(?:url\(\s*)?['"]?([^'");]+?)['"]?\s*\)?\s*;"#).unwrap();
+    let url_pattern = Regex::new(r#"url\(\s*['"]?([^'")]+)['"]?\s*\)"#).unwrap();
+
+    let import_spans: Vec<(usize, usize)> = import_pattern
+        .find_iter(content)
+        .map(|m| (m.start(), m.end()))
+        .collect();
+
+    let mut targets = Vec::new();
+    for cap in import_pattern.captures_iter(content) {
+        push_css_target(&mut targets, &cap[1]);
+    }
+    for cap in url_pattern.captures_iter(content) {
+        let whole = cap.get(0).unwrap();
+        // A `url(...)` nested inside an `@import url(...);` was already
+        // captured by import_pattern above - skip it to avoid double-counting.
+        if import_spans
+            .iter()
+            .any(|&(start, end)| whole.start() >= start && whole.end() <= end)
+        {
+            continue;
+        }
+        push_css_target(&mut targets, &cap[1]);
+    }
+    targets
+}
+
+fn push_css_target(targets: &mut Vec<String>, raw: &str) {
+    let target = raw.trim();
+    if target.is_empty()
+        || target.starts_with("http://")
+        || target.starts_with("https://")
+        || target.starts_with("//")
+        || target.starts_with("data:")
+    {
+        return;
+    }
+    targets.push(target.to_string());
+}
+
+#[cfg(test)]
+mod extract_js_css_link_targets_tests {
+    use super::*;
+
+    #[test]
+    fn js_finds_import_export_from_and_require_targets() {
+        let content = r#"
+            import './utils.js';
+            import Button from "./components/button.js";
+            export { helper } from './helper.js';
+            const legacy = require('./legacy.js');
+        "#;
+
+        let targets = extract_js_link_targets(content);
+
+        assert_eq!(targets, vec!["./utils.js", "./components/button.js", "./helper.js", "./legacy.js"]);
+    }
+
+    #[test]
+    fn css_finds_import_and_url_targets() {
+        let content = r#"
+            @import url('base.css');
+            .logo { background: url("img/logo.png"); }
+        "#;
+
+        let targets = extract_css_link_targets(content);
+
+        assert_eq!(targets, vec!["base.css", "img/logo.png"]);
+    }
+
+    #[test]
+    fn css_skips_remote_and_data_urls() {
+        let content = r#"
+            @import url('https://fonts.example.com/font.css');
+            .icon { background: url(data:image/png;base64,AAAA); }
+            .bg { background: url(//cdn.example.com/bg.png); }
+        "#;
+
+        let targets = extract_css_link_targets(content);
+
+        assert!(targets.is_empty());
+    }
+}
+
+/// Extraction for asset types we don't have a parser for - always dead-end.
+/// They're still discovered and checked for liveness, just never treated
+/// as a source of outgoing references.
+fn extract_no_link_targets(_content: &str) -> Vec<String> {
+    Vec::new()
+}
+
+fn file_type_label(file_type: &FileType) -> &str {
+    match file_type {
+        FileType::Php => "php",
+        FileType::JavaScript => "javascript",
+        FileType::Css => "css",
+        FileType::Other(ext) => ext,
+    }
+}
+
+fn sarif_result(rule_id: &str, level: &str, message: &str, relative_path: &str) -> serde_json::Value {
+    serde_json::json!({
+        "ruleId": rule_id,
+        "level": level,
+        "message": {"text": message},
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": {"uri": relative_path},
+            },
+        }],
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RootKind {
+    Plugin,
+    Theme,
+    MuPlugin,
+}
+
+fn root_kind_label(kind: &RootKind) -> &'static str {
+    match kind {
+        RootKind::Plugin => "plugin",
+        RootKind::Theme => "theme",
+        RootKind::MuPlugin => "mu-plugin",
+    }
+}
+
+/// An entry point discovered by `find_roots`, with whatever header metadata
+/// it carries. A mu-plugin root without a `Plugin Name:` line always has
+/// `name` and `uri` set to `None`; its `version`/`text_domain` are still
+/// filled in when the file has `Version:`/`Text Domain:` lines.
+#[derive(Debug, Clone)]
+struct RootInfo {
+    path: PathBuf,
+    kind: RootKind,
+    name: Option<String>,
+    version: Option<String>,
+    uri: Option<String>,
+    text_domain: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FileReport {
+    path: String,
+    file_type: String,
+    referenced_by: Vec<String>,
+    referenced_in_comments: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct RootReport {
+    path: String,
+    kind: String,
+    name: Option<String>,
+    version: Option<String>,
+    uri: Option<String>,
+    text_domain: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ScanReport {
+    root_path: String,
+    roots: Vec<RootReport>,
+    dead: Vec<FileReport>,
+    commented_dead: Vec<FileReport>,
+    alive: Vec<FileReport>,
 }
 
 struct DeadCodeFinder {
     root_path: PathBuf,
     files: HashMap<PathBuf, FileInfo>,
-    php_files: Vec<PathBuf>,
+    // Directed reference graphs keyed by referrer -> referenced targets.
+    // Kept separate so "commented-dead" files can be told apart from truly
+    // unreachable ones during the reachability pass in `find_dead_files`.
+    code_edges: HashMap<PathBuf, Vec<PathBuf>>,
+    comment_edges: HashMap<PathBuf, Vec<PathBuf>>,
 }
 
 impl DeadCodeFinder {
@@ -65,42 +552,45 @@ impl DeadCodeFinder {
         Self {
             root_path,
             files: HashMap::new(),
-            php_files: Vec::new(),
+            code_edges: HashMap::new(),
+            comment_edges: HashMap::new(),
         }
     }
 
-    fn discover_files(&mut self, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+    fn discover_files(&mut self, verbose: bool, scan_config: &ScanConfig) -> Result<(), Box<dyn std::error::Error>> {
         for entry in WalkDir::new(&self.root_path)
             .into_iter()
+            .filter_entry(|e| !scan_config.is_excluded(e.path()))
             .filter_map(|e| e.ok())
         {
             let path = entry.path();
-            if path.is_file() {
+            if path.is_file() && scan_config.is_included(path) {
                 if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                    if let Some(file_type) = FileType::from_extension(ext) {
+                    if let Some(file_type) = FileType::from_extension(ext, &scan_config.extensions) {
+                        // Canonicalize so every path we ever store, compare or
+                        // resolve against lines up with what `resolve_path` returns.
+                        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
                         let file_info = FileInfo {
-                            path: path.to_path_buf(),
+                            path: canonical.clone(),
                             file_type: file_type.clone(),
                             referenced_by: Vec::new(),
                             referenced_in_comments: Vec::new(),
                         };
 
-                        if file_type == FileType::Php {
-                            self.php_files.push(path.to_path_buf());
-                        }
-
                         if verbose {
-                            if let Ok(relative) = path.strip_prefix(&self.root_path) {
+                            if let Ok(relative) = canonical.strip_prefix(&self.root_path) {
                                 let icon = match file_type {
                                     FileType::Php => "🐘",
                                     FileType::JavaScript => "📜",
                                     FileType::Css => "🎨",
+                                    FileType::Other(_) => "📄",
                                 };
                                 println!("  {} Found: {}", icon, relative.display().to_string().dimmed());
                             }
                         }
 
-                        self.files.insert(path.to_path_buf(), file_info);
+                        self.files.insert(canonical, file_info);
                     }
                 }
             }
@@ -108,66 +598,174 @@ impl DeadCodeFinder {
         Ok(())
     }
 
-    fn find_references(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Create a list of all filenames to search for
-        let filenames: Vec<(PathBuf, String)> = self.files.keys()
-            .filter_map(|path| {
-                path.file_name()
-                    .and_then(|name| name.to_str())
-                    .map(|name| (path.clone(), name.to_string()))
-            })
-            .collect();
+    /// Reads and extracts references for a single file. Pure with respect to
+    /// `self` (only `root_path`, `files` keys and `resolve_path` are touched,
+    /// never mutated) so it's safe to call from every thread in the rayon
+    /// pool at once; callers merge the returned edges in afterwards.
+    fn scan_file_references(
+        &self,
+        referrer: &Path,
+        file_type: &FileType,
+        single_line_comment: &Regex,
+        multi_line_comment: &Regex,
+        hash_comment: &Regex,
+    ) -> Result<Vec<(PathBuf, bool)>, std::io::Error> {
+        let content = fs::read_to_string(referrer)?;
 
-        // Patterns to detect comments
-        let single_line_comment = Regex::new(r"//.*")?;
-        let multi_line_comment = Regex::new(r"(?s)/\*.*?\*/")?;
-        let hash_comment = Regex::new(r"#.*")?;
-
-        for php_file in &self.php_files.clone() {
-            let content = fs::read_to_string(php_file)?;
-            
-            // Remove comments to get clean content
-            let mut clean_content = multi_line_comment.replace_all(&content, "").to_string();
+        // Remove comments to get clean content. CSS has no `//` line
+        // comments and none of these file types use `#` comments except PHP.
+        let mut clean_content = multi_line_comment.replace_all(&content, "").to_string();
+        if *file_type != FileType::Css {
             clean_content = single_line_comment.replace_all(&clean_content, "").to_string();
+        }
+        if *file_type == FileType::Php {
             clean_content = hash_comment.replace_all(&clean_content, "").to_string();
+        }
 
-            // Extract only comment content
-            let mut comment_content = String::new();
+        // Extract only comment content
+        let mut comment_content = String::new();
+        if *file_type != FileType::Css {
             for cap in single_line_comment.captures_iter(&content) {
                 comment_content.push_str(&cap[0]);
                 comment_content.push('\n');
             }
-            for cap in multi_line_comment.captures_iter(&content) {
-                comment_content.push_str(&cap[0]);
-                comment_content.push('\n');
-            }
+        }
+        for cap in multi_line_comment.captures_iter(&content) {
+            comment_content.push_str(&cap[0]);
+            comment_content.push('\n');
+        }
+        if *file_type == FileType::Php {
             for cap in hash_comment.captures_iter(&content) {
                 comment_content.push_str(&cap[0]);
                 comment_content.push('\n');
             }
+        }
+
+        let extract = match file_type {
+            FileType::Php => extract_php_link_targets,
+            FileType::JavaScript => extract_js_link_targets,
+            FileType::Css => extract_css_link_targets,
+            FileType::Other(_) => extract_no_link_targets,
+        };
+
+        let code_targets: HashSet<String> = extract(&clean_content).into_iter().collect();
+        let comment_targets: HashSet<String> = extract(&comment_content)
+            .into_iter()
+            .filter(|target| !code_targets.contains(target))
+            .collect();
+
+        // `is_comment` tags each resolved edge so the caller knows which
+        // graph (code vs. comment) it belongs to.
+        let mut edges = Vec::new();
+        for referenced_path in &code_targets {
+            let target = self.resolve_path(referrer, referenced_path);
+            if target == referrer || !self.files.contains_key(&target) {
+                continue;
+            }
+            edges.push((target, false));
+        }
+        for referenced_path in &comment_targets {
+            let target = self.resolve_path(referrer, referenced_path);
+            if target == referrer || !self.files.contains_key(&target) {
+                continue;
+            }
+            edges.push((target, true));
+        }
+
+        Ok(edges)
+    }
 
-            // Check each filename
-            for (file_path, filename) in &filenames {
-                if file_path == php_file {
-                    continue; // Skip self-reference
+    fn find_references(&mut self, verbose: bool) -> Result<(), Box<dyn std::error::Error>> {
+        // Patterns to detect comments
+        let single_line_comment = Regex::new(r"//.*")?;
+        let multi_line_comment = Regex::new(r"(?s)/\*.*?\*/")?;
+        let hash_comment = Regex::new(r"#.*")?;
+
+        // PHP, JS and CSS can all reference other source files, so every
+        // discovered file is a potential referrer.
+        let referrers: Vec<(PathBuf, FileType)> = self
+            .files
+            .iter()
+            .map(|(path, info)| (path.clone(), info.file_type.clone()))
+            .collect();
+
+        let total = referrers.len();
+        let scanned = AtomicUsize::new(0);
+
+        // Read and pre-process each file exactly once, in parallel, instead
+        // of re-running the regex passes sequentially for every file.
+        let per_file_edges: Vec<(PathBuf, Vec<(PathBuf, bool)>)> = referrers
+            .par_iter()
+            .map(|(referrer, file_type)| {
+                let edges = self
+                    .scan_file_references(referrer, file_type, &single_line_comment, &multi_line_comment, &hash_comment)
+                    .unwrap_or_else(|err| {
+                        eprintln!(
+                            "⚠️  Warning: could not read {} ({err}), treating it as having no references",
+                            referrer.display()
+                        );
+                        Vec::new()
+                    });
+
+                if verbose {
+                    let done = scanned.fetch_add(1, Ordering::Relaxed) + 1;
+                    print!("\r  {} scanned {}/{} files", "⏳".dimmed(), done, total);
+                    let _ = std::io::Write::flush(&mut std::io::stdout());
                 }
 
-                let found_in_clean = clean_content.contains(filename);
-                let found_in_comments = comment_content.contains(filename);
+                (referrer.clone(), edges)
+            })
+            .collect();
+
+        if verbose && total > 0 {
+            println!();
+        }
 
-                if let Some(file_info) = self.files.get_mut(file_path) {
-                    if found_in_clean {
-                        file_info.referenced_by.push(php_file.clone());
-                    } else if found_in_comments {
-                        file_info.referenced_in_comments.push(php_file.clone());
+        // Merge is single-threaded since it mutates the shared HashMaps.
+        for (referrer, edges) in per_file_edges {
+            for (target, is_comment) in edges {
+                if is_comment {
+                    self.comment_edges.entry(referrer.clone()).or_default().push(target.clone());
+                    if let Some(file_info) = self.files.get_mut(&target) {
+                        file_info.referenced_in_comments.push(referrer.clone());
+                    }
+                } else {
+                    self.code_edges.entry(referrer.clone()).or_default().push(target.clone());
+                    if let Some(file_info) = self.files.get_mut(&target) {
+                        file_info.referenced_by.push(referrer.clone());
                     }
                 }
             }
         }
-        
+
         Ok(())
     }
 
+    /// Follows `code_edges` (and, when `include_comments` is set, `comment_edges`
+    /// too) outward from `roots`, returning every file reachable by that walk.
+    /// Running this from the code graph alone gives true "alive" files; unioning
+    /// in the comment graph is what lets `find_dead_files` tell apart files that
+    /// are wholly unreferenced from ones only mentioned in a comment.
+    fn reachable_from(&self, roots: &HashSet<PathBuf>, include_comments: bool) -> HashSet<PathBuf> {
+        let mut visited: HashSet<PathBuf> = roots.clone();
+        let mut queue: VecDeque<PathBuf> = roots.iter().cloned().collect();
+
+        while let Some(current) = queue.pop_front() {
+            let mut edges: Vec<&PathBuf> = self.code_edges.get(&current).into_iter().flatten().collect();
+            if include_comments {
+                edges.extend(self.comment_edges.get(&current).into_iter().flatten());
+            }
+
+            for target in edges {
+                if visited.insert(target.clone()) {
+                    queue.push_back(target.clone());
+                }
+            }
+        }
+
+        visited
+    }
+
     fn resolve_path(&self, base_file: &Path, referenced_path: &str) -> PathBuf {
         // First try relative to the base file's directory
         let base_dir = base_file.parent().unwrap_or(&self.root_path);
@@ -188,7 +786,7 @@ impl DeadCodeFinder {
             .and_then(|name| name.to_str())
             .unwrap_or(referenced_path);
         
-        for (existing_path, _) in &self.files {
+        for existing_path in self.files.keys() {
             if let Some(existing_basename) = existing_path.file_name().and_then(|name| name.to_str()) {
                 if existing_basename == referenced_basename {
                     return existing_path.clone();
@@ -200,29 +798,75 @@ impl DeadCodeFinder {
         base_dir.join(referenced_path)
     }
 
-    fn find_root_files(&self) -> HashSet<PathBuf> {
-        let mut roots = HashSet::new();
+    /// Finds every entry point in the tree: plugin files (`Plugin Name:`),
+    /// theme files (`Theme Name:`, e.g. a theme's `functions.php`), and
+    /// must-use plugins (any PHP file directly under an `mu-plugins`
+    /// directory, which WordPress loads unconditionally whether or not it
+    /// carries a header). A repo can contain several of each, so all matches
+    /// are collected rather than returning on the first hit.
+    fn find_roots(&self) -> Vec<RootInfo> {
+        let name_pattern = Regex::new(r"(?m)^[ \t]*(?:[*/]+)?[ \t]*(Plugin Name|Theme Name)[ \t]*:[ \t]*(.+?)[ \t]*$").unwrap();
+        let version_pattern = Regex::new(r"(?mi)^[ \t]*(?:[*/]+)?[ \t]*Version[ \t]*:[ \t]*(.+?)[ \t]*$").unwrap();
+        let uri_pattern = Regex::new(r"(?mi)^[ \t]*(?:[*/]+)?[ \t]*(?:Plugin|Theme) URI[ \t]*:[ \t]*(.+?)[ \t]*$").unwrap();
+        let text_domain_pattern = Regex::new(r"(?mi)^[ \t]*(?:[*/]+)?[ \t]*Text Domain[ \t]*:[ \t]*(.+?)[ \t]*$").unwrap();
 
-        // Look for WordPress plugin header (the plugin root file)
-        // Plugin Name: is a required field in WordPress plugin headers
-        let plugin_header_pattern = Regex::new(r"(?m)^\s*\*\s*Plugin Name:").unwrap();
+        let mut roots = Vec::new();
 
         for (path, file_info) in &self.files {
-            if file_info.file_type == FileType::Php {
-                if let Ok(content) = fs::read_to_string(path) {
-                    if plugin_header_pattern.is_match(&content) {
-                        roots.insert(path.clone());
-                        return roots; // WordPress plugins have only one root file
-                    }
-                }
+            if file_info.file_type != FileType::Php {
+                continue;
+            }
+
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+
+            let is_mu_plugin = path
+                .parent()
+                .and_then(|dir| dir.file_name())
+                .and_then(|name| name.to_str())
+                == Some("mu-plugins");
+
+            if let Some(caps) = name_pattern.captures(&content) {
+                let kind = if &caps[1] == "Theme Name" { RootKind::Theme } else { RootKind::Plugin };
+                roots.push(RootInfo {
+                    path: path.clone(),
+                    kind,
+                    name: Some(caps[2].to_string()),
+                    version: version_pattern.captures(&content).map(|c| c[1].to_string()),
+                    uri: uri_pattern.captures(&content).map(|c| c[1].to_string()),
+                    text_domain: text_domain_pattern.captures(&content).map(|c| c[1].to_string()),
+                });
+            } else if is_mu_plugin {
+                roots.push(RootInfo {
+                    path: path.clone(),
+                    kind: RootKind::MuPlugin,
+                    name: None,
+                    version: version_pattern.captures(&content).map(|c| c[1].to_string()),
+                    uri: None,
+                    text_domain: text_domain_pattern.captures(&content).map(|c| c[1].to_string()),
+                });
             }
         }
 
         roots
     }
 
+    fn find_root_files(&self) -> HashSet<PathBuf> {
+        self.find_roots().into_iter().map(|root| root.path).collect()
+    }
+
     fn find_dead_files(&self) -> (Vec<&FileInfo>, Vec<&FileInfo>) {
         let roots = self.find_root_files();
+
+        // True reachability from the root set, rather than "does anything
+        // point at me", is what lets this collapse an entire dead subtree
+        // (e.g. a dead admin.php that includes a dead admin-helpers.php)
+        // instead of reporting each member of the cluster as alive because
+        // something unreachable happens to reference it.
+        let reachable_code = self.reachable_from(&roots, false);
+        let reachable_with_comments = self.reachable_from(&roots, true);
+
         let mut dead_files = Vec::new();
         let mut commented_dead_files = Vec::new();
 
@@ -231,44 +875,59 @@ impl DeadCodeFinder {
                 continue; // Skip root files
             }
 
-            let is_referenced = !file_info.referenced_by.is_empty();
-            let is_commented = !file_info.referenced_in_comments.is_empty();
+            if reachable_code.contains(path) {
+                continue; // Alive
+            }
 
-            if !is_referenced {
-                if is_commented {
-                    commented_dead_files.push(file_info);
-                } else {
-                    dead_files.push(file_info);
-                }
+            if reachable_with_comments.contains(path) {
+                commented_dead_files.push(file_info);
+            } else {
+                dead_files.push(file_info);
             }
         }
 
         (dead_files, commented_dead_files)
     }
 
-    fn print_results(&self, dead_files: &[&FileInfo], commented_dead_files: &[&FileInfo], verbose: bool) {
+    /// Files that are neither a root, dead, nor commented-dead - i.e. reachable
+    /// from a root via the code graph. Shared by the text, JSON and SARIF
+    /// output paths so "alive" means the same thing in all three.
+    fn alive_files(&self, dead_files: &[&FileInfo], commented_dead_files: &[&FileInfo]) -> Vec<&FileInfo> {
         let roots = self.find_root_files();
+        let dead_paths: HashSet<&PathBuf> = dead_files.iter()
+            .chain(commented_dead_files.iter())
+            .map(|f| &f.path)
+            .collect();
+
+        self.files.values()
+            .filter(|f| !roots.contains(&f.path) && !dead_paths.contains(&f.path))
+            .collect()
+    }
+
+    fn print_results(&self, dead_files: &[&FileInfo], commented_dead_files: &[&FileInfo], verbose: bool) {
+        let roots = self.find_roots();
 
         if verbose {
             println!("\n{}", "=== Analysis Results ===".cyan().bold());
             println!();
             println!("{}", "Root files (not considered dead):".cyan().bold());
             for root in &roots {
-                if let Ok(relative) = root.strip_prefix(&self.root_path) {
-                    println!("  📁 {}", relative.display().to_string().blue());
+                let relative = self.relative_path(&root.path);
+                let mut line = format!("  📁 [{}] {}", root_kind_label(&root.kind), relative.blue());
+                if let Some(name) = &root.name {
+                    line.push_str(&format!(" - {}", name));
+                }
+                if let Some(version) = &root.version {
+                    line.push_str(&format!(" v{}", version));
+                }
+                if let Some(text_domain) = &root.text_domain {
+                    line.push_str(&format!(" (text domain: {})", text_domain));
                 }
+                println!("{}", line);
             }
             println!();
 
-            // Show all alive files (referenced and not dead)
-            let dead_paths: HashSet<&PathBuf> = dead_files.iter()
-                .chain(commented_dead_files.iter())
-                .map(|f| &f.path)
-                .collect();
-
-            let alive_files: Vec<&FileInfo> = self.files.values()
-                .filter(|f| !roots.contains(&f.path) && !dead_paths.contains(&f.path))
-                .collect();
+            let alive_files = self.alive_files(dead_files, commented_dead_files);
 
             if !alive_files.is_empty() {
                 println!("{}", "Alive files (referenced in code):".green().bold());
@@ -278,6 +937,7 @@ impl DeadCodeFinder {
                             FileType::Php => "🐘",
                             FileType::JavaScript => "📜",
                             FileType::Css => "🎨",
+                            FileType::Other(_) => "📄",
                         };
                         println!("  {} {} (referenced by {} file(s))",
                             icon,
@@ -297,6 +957,7 @@ impl DeadCodeFinder {
                         FileType::Php => "🐘",
                         FileType::JavaScript => "📜",
                         FileType::Css => "🎨",
+                        FileType::Other(_) => "📄",
                     };
                     println!("  {} {}", icon, relative.display().to_string().red());
                 }
@@ -312,6 +973,7 @@ impl DeadCodeFinder {
                         FileType::Php => "🐘",
                         FileType::JavaScript => "📜",
                         FileType::Css => "🎨",
+                        FileType::Other(_) => "📄",
                     };
                     println!("  {} {}", icon, relative.display().to_string().yellow());
                     if verbose {
@@ -335,6 +997,76 @@ impl DeadCodeFinder {
         }
     }
 
+    fn relative_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root_path).unwrap_or(path).display().to_string()
+    }
+
+    fn file_report(&self, file_info: &FileInfo) -> FileReport {
+        FileReport {
+            path: self.relative_path(&file_info.path),
+            file_type: file_type_label(&file_info.file_type).to_string(),
+            referenced_by: file_info.referenced_by.iter().map(|p| self.relative_path(p)).collect(),
+            referenced_in_comments: file_info.referenced_in_comments.iter().map(|p| self.relative_path(p)).collect(),
+        }
+    }
+
+    fn build_report(&self, dead_files: &[&FileInfo], commented_dead_files: &[&FileInfo]) -> ScanReport {
+        let roots = self.find_roots().into_iter().map(|root| RootReport {
+            path: self.relative_path(&root.path),
+            kind: root_kind_label(&root.kind).to_string(),
+            name: root.name,
+            version: root.version,
+            uri: root.uri,
+            text_domain: root.text_domain,
+        }).collect();
+
+        ScanReport {
+            root_path: self.root_path.display().to_string(),
+            roots,
+            dead: dead_files.iter().map(|f| self.file_report(f)).collect(),
+            commented_dead: commented_dead_files.iter().map(|f| self.file_report(f)).collect(),
+            alive: self.alive_files(dead_files, commented_dead_files).iter().map(|f| self.file_report(f)).collect(),
+        }
+    }
+
+    /// Builds a minimal SARIF 2.1.0 log with one result per dead/commented-dead
+    /// file, so the findings can be uploaded as code-review annotations in
+    /// GitHub/GitLab rather than only read from a terminal.
+    fn build_sarif(&self, dead_files: &[&FileInfo], commented_dead_files: &[&FileInfo]) -> serde_json::Value {
+        let mut results = Vec::new();
+
+        for file in dead_files {
+            results.push(sarif_result("dead-file", "error", "This file is not referenced from any root file.", &self.relative_path(&file.path)));
+        }
+        for file in commented_dead_files {
+            let referrers: Vec<String> = file.referenced_in_comments.iter().map(|p| self.relative_path(p)).collect();
+            results.push(sarif_result(
+                "commented-dead-file",
+                "warning",
+                &format!("This file is only referenced from a comment in: {}.", referrers.join(", ")),
+                &self.relative_path(&file.path),
+            ));
+        }
+
+        serde_json::json!({
+            "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "fdc",
+                        "informationUri": "https://github.com/dhilditch/fdc",
+                        "rules": [
+                            {"id": "dead-file", "shortDescription": {"text": "File not reachable from any root file"}},
+                            {"id": "commented-dead-file", "shortDescription": {"text": "File only referenced in a comment"}},
+                        ],
+                    },
+                },
+                "results": results,
+            }],
+        })
+    }
+
     fn delete_files(&self, files: &[&FileInfo]) -> Result<(), Box<dyn std::error::Error>> {
         for file in files {
             println!("Deleting: {}", file.path.display().to_string().red());
@@ -344,33 +1076,107 @@ impl DeadCodeFinder {
     }
 }
 
+#[cfg(test)]
+mod reachability_tests {
+    use super::*;
+
+    fn finder_with_edges(code_edges: &[(&str, &str)], comment_edges: &[(&str, &str)]) -> DeadCodeFinder {
+        let mut finder = DeadCodeFinder::new(PathBuf::from("/project"));
+        for (from, to) in code_edges {
+            finder.code_edges.entry(PathBuf::from(from)).or_default().push(PathBuf::from(to));
+        }
+        for (from, to) in comment_edges {
+            finder.comment_edges.entry(PathBuf::from(from)).or_default().push(PathBuf::from(to));
+        }
+        finder
+    }
+
+    #[test]
+    fn reachable_from_follows_code_edges_transitively() {
+        let finder = finder_with_edges(&[("/project/root.php", "/project/a.php"), ("/project/a.php", "/project/b.php")], &[]);
+        let roots: HashSet<PathBuf> = [PathBuf::from("/project/root.php")].into_iter().collect();
+
+        let reachable = finder.reachable_from(&roots, false);
+
+        assert!(reachable.contains(Path::new("/project/a.php")));
+        assert!(reachable.contains(Path::new("/project/b.php")));
+    }
+
+    #[test]
+    fn orphaned_cluster_referencing_itself_is_not_reachable() {
+        // admin.php <-> admin-helpers.php reference each other but neither
+        // is reachable from the root, so both should stay unreachable.
+        let finder = finder_with_edges(
+            &[("/project/admin.php", "/project/admin-helpers.php"), ("/project/admin-helpers.php", "/project/admin.php")],
+            &[],
+        );
+        let roots: HashSet<PathBuf> = [PathBuf::from("/project/root.php")].into_iter().collect();
+
+        let reachable = finder.reachable_from(&roots, false);
+
+        assert!(!reachable.contains(Path::new("/project/admin.php")));
+        assert!(!reachable.contains(Path::new("/project/admin-helpers.php")));
+    }
+
+    #[test]
+    fn comment_only_edge_requires_include_comments() {
+        let finder = finder_with_edges(&[], &[("/project/root.php", "/project/legacy.php")]);
+        let roots: HashSet<PathBuf> = [PathBuf::from("/project/root.php")].into_iter().collect();
+
+        assert!(!finder.reachable_from(&roots, false).contains(Path::new("/project/legacy.php")));
+        assert!(finder.reachable_from(&roots, true).contains(Path::new("/project/legacy.php")));
+    }
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
-    let root_path = cli.path.unwrap_or_else(|| std::env::current_dir().unwrap());
+    let root_path = cli.path.clone().unwrap_or_else(|| std::env::current_dir().unwrap());
 
     if !root_path.exists() {
         eprintln!("Error: Path '{}' does not exist", root_path.display());
         std::process::exit(1);
     }
 
-    println!("🔍 Scanning for dead code in: {}", root_path.display().to_string().cyan());
+    let root_path = root_path.canonicalize().unwrap_or(root_path);
+
+    let scan_config = ScanConfig::from_cli(&cli, &root_path)?;
 
-    if cli.verbose {
-        println!("\n{}", "Discovering files...".dimmed());
+    // Structured formats are meant to be piped straight into a CI step, so
+    // the scan's progress and summary lines are only printed for text output
+    // (the --delete prompt below still goes to stdout in every format).
+    let human_output = cli.format == OutputFormat::Text;
+
+    if human_output {
+        println!("🔍 Scanning for dead code in: {}", root_path.display().to_string().cyan());
+        if cli.verbose {
+            println!("\n{}", "Discovering files...".dimmed());
+        }
     }
 
     let mut finder = DeadCodeFinder::new(root_path);
 
-    finder.discover_files(cli.verbose)?;
-    println!("\n📊 Found {} files to analyze", finder.files.len());
-    
-    finder.find_references()?;
-    
+    finder.discover_files(cli.verbose && human_output, &scan_config)?;
+    if human_output {
+        println!("\n📊 Found {} files to analyze", finder.files.len());
+    }
+
+    finder.find_references(cli.verbose && human_output)?;
+
     let (dead_files, commented_dead_files) = finder.find_dead_files();
-    
-    finder.print_results(&dead_files, &commented_dead_files, cli.verbose);
-    
+
+    match cli.format {
+        OutputFormat::Text => finder.print_results(&dead_files, &commented_dead_files, cli.verbose),
+        OutputFormat::Json => {
+            let report = finder.build_report(&dead_files, &commented_dead_files);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        OutputFormat::Sarif => {
+            let sarif = finder.build_sarif(&dead_files, &commented_dead_files);
+            println!("{}", serde_json::to_string_pretty(&sarif)?);
+        }
+    }
+
     if cli.delete && (!dead_files.is_empty() || !commented_dead_files.is_empty()) {
         println!("\n{}", "⚠️  DELETE MODE ENABLED".red().bold());
         println!("This will permanently delete the identified dead files.");
@@ -386,6 +1192,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Note: Files only referenced in comments were not deleted for safety.");
         }
     }
-    
+
+    // Dead files were left in place (not deleted), so fail the build - this
+    // is what lets `fdc --format json|sarif` gate a CI job.
+    if !dead_files.is_empty() && !cli.delete {
+        std::process::exit(1);
+    }
+
     Ok(())
 }
\ No newline at end of file